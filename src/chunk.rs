@@ -1,39 +1,123 @@
+#[cfg(feature = "std")]
 use anyhow::bail;
-use crc::CRC_32_ISO_HDLC;
+#[cfg(feature = "std")]
+use bytes::Bytes;
+use crc::{Crc, Digest, CRC_32_ISO_HDLC};
+#[cfg(feature = "std")]
 use std::fmt::Display;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
 
 use crate::chunk_type::ChunkType;
+#[cfg(feature = "heapless")]
+use crate::chunk_type::ChunkTypeError;
+#[cfg(feature = "std")]
+use crate::codec::{eof_at, CountingReader, Decode, DecodeError, Encode};
+
+static CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// Upper bound on a chunk's declared data length, enforced before any
+/// allocation happens. The PNG spec only caps chunk length at `i32::MAX`
+/// (~2 GiB), which is no defense at all against a hostile or corrupt
+/// length field: both `TryFrom<Bytes>` and `Decode` used to allocate
+/// `length` bytes up front, so a handful of header bytes claiming a
+/// multi-GB chunk could exhaust memory before a single data byte was read
+/// or checked against what the source actually has left.
+///
+/// This is an intentional product/security tightening beyond what the PNG
+/// spec requires, not just a mechanical bugfix: a spec-valid chunk between
+/// 256 MiB and `i32::MAX` (an unusually large single `IDAT`, say) is now
+/// rejected outright rather than merely capped at the spec limit. 256 MiB
+/// was picked as comfortably above any real-world ancillary chunk while
+/// still bounding worst-case allocation to something sane for this crate's
+/// use case; revisit it if a legitimate caller needs larger chunks.
+#[cfg(feature = "std")]
+const MAX_CHUNK_LEN: u32 = 1 << 28;
+
+fn checksum(chunk_type: &ChunkType, data: &[u8]) -> u32 {
+    let mut digest: Digest<u32> = CRC_32.digest();
+    digest.update(&chunk_type.bytes());
+    digest.update(data);
+    digest.finalize()
+}
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub(crate) struct Chunk {
     length: u32,
     chunk_type: ChunkType,
-    data: Vec<u8>,
+    data: Bytes,
     crc: u32,
 }
 
-impl Chunk {
-    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk {
-        let crc = crc::Crc::<u32>::new(&CRC_32_ISO_HDLC);
+/// Validates a declared chunk length against [`MAX_CHUNK_LEN`], returning
+/// the cap on failure so each caller can build its own error (an
+/// `anyhow!` error for `TryFrom<Bytes>`, a `DecodeError` for `Decode`). Length and CRC
+/// validation must stay identical between the two parse paths, so both
+/// call this instead of duplicating the check.
+#[cfg(feature = "std")]
+fn check_length(length: u32) -> Result<(), u32> {
+    if length > MAX_CHUNK_LEN {
+        Err(MAX_CHUNK_LEN)
+    } else {
+        Ok(())
+    }
+}
 
-        // TODO: see how to avoid the copies here
-        let data_crc = chunk_type
-            .bytes()
-            .iter()
-            .chain(data.iter())
-            .copied()
-            .collect::<Vec<_>>();
+/// Validates `crc` against what `chunk_type` and `data` hash to, returning
+/// the expected value on mismatch so each caller can build its own error.
+/// Shared for the same reason as [`check_length`].
+#[cfg(feature = "std")]
+fn check_crc(chunk_type: &ChunkType, data: &[u8], crc: u32) -> Result<(), u32> {
+    let expected = checksum(chunk_type, data);
+    if crc == expected {
+        Ok(())
+    } else {
+        Err(expected)
+    }
+}
 
-        let checksum = crc.checksum(&data_crc);
+#[cfg(feature = "std")]
+impl Chunk {
+    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk {
+        let data = Bytes::from(data);
+        let crc = checksum(&chunk_type, &data);
 
         Self {
             length: data.len() as u32,
             chunk_type,
             data,
-            crc: checksum,
+            crc,
         }
     }
 
+    /// Replaces `data` (and `length`) and recomputes `crc` to match.
+    ///
+    /// `data` is an immutable `Bytes`, so there's no in-place mutable
+    /// accessor to keep in sync; this is the supported way to change a
+    /// chunk's contents after construction.
+    pub fn set_data(&mut self, data: impl Into<Bytes>) {
+        self.data = data.into();
+        self.length = self.data.len() as u32;
+        self.recompute_crc();
+    }
+
+    /// Recomputes and overwrites `crc` from the current `chunk_type` and `data`.
+    ///
+    /// Kept private: `data` is an immutable `Bytes`, so there's no mutable
+    /// accessor an external caller could use to change it before calling
+    /// this, which would make a public recompute meaningless. [`Self::set_data`]
+    /// is the public entry point that mutates `data` and recomputes `crc`
+    /// together.
+    fn recompute_crc(&mut self) {
+        self.crc = checksum(&self.chunk_type, &self.data);
+    }
+
+    /// Returns whether `crc` matches what `chunk_type` and `data` hash to.
+    pub fn is_crc_valid(&self) -> bool {
+        self.crc == checksum(&self.chunk_type, &self.data)
+    }
+
     pub fn length(&self) -> u32 {
         self.length
     }
@@ -51,50 +135,58 @@ impl Chunk {
     }
 
     pub fn data_as_string(&self) -> anyhow::Result<String> {
-        Ok(String::from_utf8(self.data.clone())?)
+        Ok(std::str::from_utf8(&self.data)?.to_owned())
+    }
+
+    /// A cheap clone of the chunk's data, sharing the same underlying
+    /// allocation rather than copying it.
+    pub fn data_bytes(&self) -> Bytes {
+        self.data.clone()
     }
 
     pub fn as_bytes(&self) -> Vec<u8> {
-        self.length
-            .to_be_bytes()
-            .into_iter()
-            .chain(self.chunk_type.bytes())
-            .chain(self.data.clone().into_iter())
-            .chain(self.crc.to_be_bytes())
-            .collect()
+        let mut buf = Vec::with_capacity(self.size());
+        self.encode(&mut buf)
+            .expect("writing to a Vec<u8> is infallible");
+        buf
     }
 
-    /// Size in bytes
+    /// Size in bytes. Backed by [`Self::encoded_len`] so there's a single
+    /// source of truth for both.
     pub fn size(&self) -> usize {
-        (4 + 4 + self.length + 4) as usize
+        self.encoded_len()
     }
 }
 
-impl TryFrom<&[u8]> for Chunk {
+#[cfg(feature = "std")]
+impl TryFrom<Bytes> for Chunk {
     type Error = anyhow::Error;
 
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let length = u32::from_be_bytes(value[0..4].try_into()?);
-        if length > 2e31 as u32 {
-            bail!("length field ({}) should not exceed {}", length, 2e31)
+    fn try_from(value: Bytes) -> Result<Self, Self::Error> {
+        if value.len() < 8 {
+            bail!("chunk is shorter than its length/type/crc framing");
         }
+        let length = u32::from_be_bytes(value[0..4].try_into()?);
+        check_length(length).map_err(|max| {
+            anyhow::anyhow!("length field ({}) should not exceed {}", length, max)
+        })?;
         let slice: [u8; 4] = value[4..8].try_into()?;
         let chunk_type = ChunkType::try_from(slice)?;
         let end_range = 8 + length as usize;
-        let data: Vec<u8> = value[8..end_range].into_iter().copied().collect();
+        if value.len() < end_range + 4 {
+            bail!("chunk is shorter than its length/type/crc framing");
+        }
+        let data = value.slice(8..end_range);
 
         let crc = u32::from_be_bytes(value[end_range..end_range + 4].try_into()?);
 
-        let crc_algo = crc::Crc::<u32>::new(&CRC_32_ISO_HDLC);
-        let checksum = crc_algo.checksum(&value[4..end_range]);
-
-        if crc != checksum {
-            bail!(
+        check_crc(&chunk_type, &data, crc).map_err(|expected| {
+            anyhow::anyhow!(
                 "checksums do not match. Got `{}` expected `{}`",
                 crc,
-                checksum
+                expected
             )
-        }
+        })?;
 
         Ok(Self {
             length,
@@ -105,13 +197,309 @@ impl TryFrom<&[u8]> for Chunk {
     }
 }
 
+#[cfg(feature = "std")]
+impl TryFrom<&[u8]> for Chunk {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Bytes::copy_from_slice(value).try_into()
+    }
+}
+
+#[cfg(feature = "std")]
 impl Display for Chunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} - {} - {}", self.length, self.chunk_type, self.crc)
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "std")]
+impl Decode for Chunk {
+    fn decode(reader: &mut CountingReader<impl Read>) -> anyhow::Result<Self> {
+        let length_offset = reader.offset();
+        let mut length_buf = [0u8; 4];
+        reader
+            .read_exact(&mut length_buf)
+            .map_err(|e| eof_at(e, length_offset, "chunk length"))?;
+        let length = u32::from_be_bytes(length_buf);
+        if let Err(max) = check_length(length) {
+            return Err(DecodeError::new(
+                length_offset,
+                format!("length field ({}) should not exceed {}", length, max),
+            )
+            .into());
+        }
+
+        let chunk_type = ChunkType::decode(reader)?;
+
+        let data_offset = reader.offset();
+        let mut data = vec![0u8; length as usize];
+        reader
+            .read_exact(&mut data)
+            .map_err(|e| eof_at(e, data_offset, "chunk data"))?;
+
+        let crc_offset = reader.offset();
+        let mut crc_buf = [0u8; 4];
+        reader
+            .read_exact(&mut crc_buf)
+            .map_err(|e| eof_at(e, crc_offset, "chunk crc"))?;
+        let crc = u32::from_be_bytes(crc_buf);
+
+        if let Err(expected) = check_crc(&chunk_type, &data, crc) {
+            return Err(DecodeError::new(
+                crc_offset,
+                format!("invalid CRC. Got `{}` expected `{}`", crc, expected),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            length,
+            chunk_type,
+            data: Bytes::from(data),
+            crc,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Encode for Chunk {
+    fn encode(&self, writer: &mut impl Write) -> anyhow::Result<()> {
+        writer.write_all(&self.length.to_be_bytes())?;
+        self.chunk_type.encode(writer)?;
+        writer.write_all(&self.data)?;
+        writer.write_all(&self.crc.to_be_bytes())?;
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        4 + self.chunk_type.encoded_len() + self.data.len() + 4
+    }
+}
+
+/// A fixed-capacity counterpart to [`Chunk`] for firmware with no heap
+/// allocator: `data` lives in a [`heapless::Vec`] bounded by `N` bytes at
+/// compile time instead of an allocating `Vec<u8>`. Meant for small
+/// ancillary chunks (a `tEXt` or custom message chunk) where the size
+/// bound is known up front; large chunks like `IDAT` should keep using
+/// the allocating [`Chunk`].
+#[cfg(feature = "heapless")]
+#[derive(Debug)]
+pub(crate) struct FixedChunk<const N: usize> {
+    length: u32,
+    chunk_type: ChunkType,
+    data: heapless::Vec<u8, N>,
+    crc: u32,
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> FixedChunk<N> {
+    pub fn new(chunk_type: ChunkType, data: heapless::Vec<u8, N>) -> Self {
+        let crc = checksum(&chunk_type, &data);
+
+        Self {
+            length: data.len() as u32,
+            chunk_type,
+            data,
+            crc,
+        }
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+
+    pub fn is_crc_valid(&self) -> bool {
+        self.crc == checksum(&self.chunk_type, &self.data)
+    }
+
+    /// Number of bytes [`encode`](Self::encode) writes.
+    pub fn encoded_len(&self) -> usize {
+        4 + 4 + self.data.len() + 4
+    }
+
+    /// Serializes the chunk into `out` without allocating, the no-alloc
+    /// counterpart to [`Chunk::as_bytes`]. Returns the number of bytes
+    /// written, or `FixedChunkError::OutputTooSmall` if `out` can't hold
+    /// `encoded_len()` bytes.
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, FixedChunkError> {
+        let needed = self.encoded_len();
+        if out.len() < needed {
+            return Err(FixedChunkError::OutputTooSmall {
+                needed,
+                available: out.len(),
+            });
+        }
+
+        out[0..4].copy_from_slice(&self.length.to_be_bytes());
+        out[4..8].copy_from_slice(&self.chunk_type.bytes());
+        out[8..8 + self.data.len()].copy_from_slice(&self.data);
+        out[8 + self.data.len()..needed].copy_from_slice(&self.crc.to_be_bytes());
+
+        Ok(needed)
+    }
+}
+
+/// Errors from [`FixedChunk::try_from`] and [`FixedChunk::encode`]. Carries
+/// no allocation, so firmware can reject a truncated/oversized/corrupt chunk
+/// without a heap.
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FixedChunkError {
+    Truncated,
+    ChunkType(ChunkTypeError),
+    TooLarge { length: usize, capacity: usize },
+    CrcMismatch { expected: u32, actual: u32 },
+    OutputTooSmall { needed: usize, available: usize },
+}
+
+#[cfg(feature = "heapless")]
+impl core::fmt::Display for FixedChunkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "chunk is shorter than its length/type/crc framing"),
+            Self::ChunkType(e) => write!(f, "{}", e),
+            Self::TooLarge { length, capacity } => write!(
+                f,
+                "chunk data ({} bytes) exceeds fixed capacity ({} bytes)",
+                length, capacity
+            ),
+            Self::CrcMismatch { expected, actual } => {
+                write!(f, "invalid CRC. Got `{}` expected `{}`", actual, expected)
+            }
+            Self::OutputTooSmall { needed, available } => write!(
+                f,
+                "output buffer ({} bytes) is too small to hold the encoded chunk ({} bytes)",
+                available, needed
+            ),
+        }
+    }
+}
+
+#[cfg(all(feature = "heapless", feature = "std"))]
+impl std::error::Error for FixedChunkError {}
+
+/// Parses a `FixedChunk` directly out of a length/type/data/crc byte slice,
+/// the no-alloc counterpart to [`Chunk`]'s `Decode` impl: no `std::io::Read`
+/// or heap buffer required, just the bytes already in hand.
+#[cfg(feature = "heapless")]
+impl<const N: usize> TryFrom<&[u8]> for FixedChunk<N> {
+    type Error = FixedChunkError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() < 8 {
+            return Err(FixedChunkError::Truncated);
+        }
+        let length = u32::from_be_bytes(value[0..4].try_into().unwrap()) as usize;
+        if length > N {
+            return Err(FixedChunkError::TooLarge {
+                length,
+                capacity: N,
+            });
+        }
+
+        let type_bytes: [u8; 4] = value[4..8].try_into().unwrap();
+        let chunk_type = ChunkType::try_from(type_bytes).map_err(FixedChunkError::ChunkType)?;
+
+        let end = 8 + length;
+        if value.len() < end + 4 {
+            return Err(FixedChunkError::Truncated);
+        }
+
+        let mut data = heapless::Vec::new();
+        data.extend_from_slice(&value[8..end])
+            .expect("length was already checked against capacity N");
+
+        let crc = u32::from_be_bytes(value[end..end + 4].try_into().unwrap());
+        let expected_crc = checksum(&chunk_type, &data);
+        if crc != expected_crc {
+            return Err(FixedChunkError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc,
+            });
+        }
+
+        Ok(Self {
+            length: length as u32,
+            chunk_type,
+            data,
+            crc,
+        })
+    }
+}
+
+/// Pulls PNG chunks one at a time from a reader, instead of requiring the
+/// whole file to be buffered up front.
+#[cfg(feature = "std")]
+pub(crate) struct ChunkReader<R: Read> {
+    reader: CountingReader<R>,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> ChunkReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: CountingReader::new(reader),
+        }
+    }
+
+    /// Reads the next chunk, or `Ok(None)` if the reader is exhausted
+    /// exactly at a chunk boundary.
+    fn read_chunk(&mut self) -> anyhow::Result<Option<Chunk>> {
+        let start_offset = self.reader.offset();
+        match Chunk::decode(&mut self.reader) {
+            Ok(chunk) => Ok(Some(chunk)),
+            // Nothing was consumed before the failure, so this is a clean
+            // end of stream rather than a truncated chunk.
+            // A non-EOF I/O error is not a `DecodeError`, so it still surfaces.
+            Err(err) if self.reader.offset() == start_offset && err.is::<DecodeError>() => {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: Read> Iterator for ChunkReader<R> {
+    type Item = anyhow::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_chunk().transpose()
+    }
+}
+
+/// Serializes chunks one at a time to a writer, without collecting the
+/// whole chunk into an intermediate `Vec<u8>` first.
+#[cfg(feature = "std")]
+pub(crate) struct ChunkWriter<W: Write> {
+    writer: W,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> ChunkWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write_chunk(&mut self, chunk: &Chunk) -> anyhow::Result<()> {
+        chunk.encode(&mut self.writer)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::chunk_type::ChunkType;
@@ -152,6 +540,12 @@ mod tests {
         assert_eq!(chunk.length(), 42);
     }
 
+    #[test]
+    fn test_chunk_size_matches_as_bytes_len() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.size(), chunk.as_bytes().len());
+    }
+
     #[test]
     fn test_chunk_type() {
         let chunk = testing_chunk();
@@ -172,6 +566,33 @@ mod tests {
         assert_eq!(chunk.crc(), 2882656334);
     }
 
+    #[test]
+    fn test_chunk_is_crc_valid() {
+        let chunk = testing_chunk();
+        assert!(chunk.is_crc_valid());
+    }
+
+    #[test]
+    fn test_chunk_set_data_recomputes_crc_and_length() {
+        let mut chunk = testing_chunk();
+        let new_message = "shorter message".as_bytes().to_vec();
+        let expected_length = new_message.len() as u32;
+
+        chunk.set_data(new_message.clone());
+
+        assert_eq!(chunk.length(), expected_length);
+        assert_eq!(chunk.data(), new_message.as_slice());
+        assert!(chunk.is_crc_valid());
+        assert_ne!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    fn test_chunk_data_bytes_shares_allocation() {
+        let chunk = testing_chunk();
+        let data_bytes = chunk.data_bytes();
+        assert_eq!(data_bytes.as_ptr(), chunk.data().as_ptr());
+    }
+
     #[test]
     fn test_valid_chunk_from_bytes() {
         let data_length: u32 = 42;
@@ -220,6 +641,201 @@ mod tests {
         assert!(chunk.is_err());
     }
 
+    #[test]
+    fn test_chunk_reader_reads_multiple_chunks() {
+        let first = testing_chunk();
+        let second = Chunk::new(
+            ChunkType::from_str("ruSt").unwrap(),
+            "second chunk".as_bytes().to_vec(),
+        );
+        let mut bytes = first.as_bytes();
+        bytes.extend(second.as_bytes());
+
+        let mut reader = ChunkReader::new(bytes.as_slice());
+        let read_first = reader.next().unwrap().unwrap();
+        let read_second = reader.next().unwrap().unwrap();
+
+        assert_eq!(read_first.chunk_type().to_string(), "RuSt");
+        assert_eq!(read_second.chunk_type().to_string(), "ruSt");
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_chunk_reader_clean_eof_between_chunks() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+        let mut reader = ChunkReader::new(bytes.as_slice());
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_chunk_reader_reports_truncated_chunk() {
+        let chunk = testing_chunk();
+        let mut bytes = chunk.as_bytes();
+        bytes.truncate(bytes.len() - 1);
+
+        let mut reader = ChunkReader::new(bytes.as_slice());
+
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_chunk_decode_rejects_oversized_length_before_allocating() {
+        // A length field claiming a ~4GiB chunk, backed by a stream that
+        // only has a handful of bytes left. If the length guard didn't
+        // actually reject this, `Chunk::decode` would attempt a multi-GB
+        // `vec![0u8; length as usize]` before ever reading the short body.
+        let mut bytes = u32::MAX.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"RuSt");
+        bytes.extend_from_slice(b"short");
+
+        let mut reader = CountingReader::new(bytes.as_slice());
+        let err = Chunk::decode(&mut reader).unwrap_err();
+
+        let decode_err = err.downcast_ref::<DecodeError>().unwrap();
+        assert_eq!(decode_err.offset, 0);
+    }
+
+    #[test]
+    fn test_chunk_decode_rejects_length_under_i32_max_but_over_cap() {
+        // Well under the old `i32::MAX` guard (~2 GiB) but still a length no
+        // legitimate chunk would use; this is the case the old guard let
+        // through straight into `vec![0u8; length as usize]`.
+        let mut bytes = (MAX_CHUNK_LEN + 1).to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"RuSt");
+        bytes.extend_from_slice(b"short");
+
+        let mut reader = CountingReader::new(bytes.as_slice());
+        let err = Chunk::decode(&mut reader).unwrap_err();
+
+        let decode_err = err.downcast_ref::<DecodeError>().unwrap();
+        assert_eq!(decode_err.offset, 0);
+    }
+
+    #[test]
+    fn test_chunk_try_from_rejects_length_under_i32_max_but_over_cap() {
+        let mut bytes = (MAX_CHUNK_LEN + 1).to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"RuSt");
+        bytes.extend_from_slice(b"short");
+
+        assert!(Chunk::try_from(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_chunk_try_from_rejects_truncated_bytes() {
+        let chunk = Chunk::try_from([0u8, 1, 2].as_slice());
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_decode_encode_round_trip() {
+        let chunk = testing_chunk();
+        let mut buf = Vec::new();
+        chunk.encode(&mut buf).unwrap();
+        assert_eq!(chunk.encoded_len(), buf.len());
+
+        let mut reader = CountingReader::new(buf.as_slice());
+        let decoded = Chunk::decode(&mut reader).unwrap();
+        assert_eq!(decoded.crc(), chunk.crc());
+        assert_eq!(decoded.data(), chunk.data());
+    }
+
+    #[test]
+    fn test_chunk_decode_reports_offset_of_bad_crc() {
+        let chunk = testing_chunk();
+        let mut buf = chunk.as_bytes();
+        let crc_offset = buf.len() - 4;
+        buf[crc_offset] ^= 0xFF;
+
+        let mut reader = CountingReader::new(buf.as_slice());
+        let err = Chunk::decode(&mut reader).unwrap_err();
+
+        let decode_err = err.downcast_ref::<DecodeError>().unwrap();
+        assert_eq!(decode_err.offset as usize, crc_offset);
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_fixed_chunk_crc_matches_allocating_chunk() {
+        let message = "This is where your secret message will be!".as_bytes();
+        let chunk = Chunk::new(ChunkType::from_str("RuSt").unwrap(), message.to_vec());
+
+        let mut fixed_data: heapless::Vec<u8, 64> = heapless::Vec::new();
+        fixed_data.extend_from_slice(message).unwrap();
+        let fixed_chunk = FixedChunk::<64>::new(ChunkType::from_str("RuSt").unwrap(), fixed_data);
+
+        assert_eq!(fixed_chunk.crc(), chunk.crc());
+        assert!(fixed_chunk.is_crc_valid());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_fixed_chunk_try_from_slice_parses_bytes() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+
+        let fixed_chunk = FixedChunk::<64>::try_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(fixed_chunk.length(), chunk.length());
+        assert_eq!(fixed_chunk.chunk_type().to_string(), "RuSt");
+        assert_eq!(fixed_chunk.data(), chunk.data());
+        assert_eq!(fixed_chunk.crc(), chunk.crc());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_fixed_chunk_try_from_slice_rejects_oversized_data() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+
+        let err = FixedChunk::<4>::try_from(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, FixedChunkError::TooLarge { .. }));
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_fixed_chunk_encode_round_trips_through_try_from() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+        let fixed_chunk = FixedChunk::<64>::try_from(bytes.as_slice()).unwrap();
+
+        let mut out = [0u8; 128];
+        let written = fixed_chunk.encode(&mut out).unwrap();
+        assert_eq!(written, fixed_chunk.encoded_len());
+        assert_eq!(&out[..written], bytes.as_slice());
+
+        let decoded = FixedChunk::<64>::try_from(&out[..written]).unwrap();
+        assert_eq!(decoded.data(), fixed_chunk.data());
+        assert_eq!(decoded.crc(), fixed_chunk.crc());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_fixed_chunk_encode_rejects_too_small_buffer() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+        let fixed_chunk = FixedChunk::<64>::try_from(bytes.as_slice()).unwrap();
+
+        let mut out = [0u8; 4];
+        let err = fixed_chunk.encode(&mut out).unwrap_err();
+        assert!(matches!(err, FixedChunkError::OutputTooSmall { .. }));
+    }
+
+    #[test]
+    fn test_chunk_writer_round_trips_through_reader() {
+        let chunk = testing_chunk();
+        let mut out = Vec::new();
+        ChunkWriter::new(&mut out).write_chunk(&chunk).unwrap();
+
+        let mut reader = ChunkReader::new(out.as_slice());
+        let read_back = reader.next().unwrap().unwrap();
+
+        assert_eq!(read_back.crc(), chunk.crc());
+        assert_eq!(read_back.data(), chunk.data());
+    }
+
     #[test]
     pub fn test_chunk_trait_impls() {
         let data_length: u32 = 42;
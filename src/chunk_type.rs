@@ -1,12 +1,45 @@
-use std::{fmt::Display, str::FromStr};
+//! `no_std`-clean: this module only ever touches a stack-allocated
+//! `[u8; 4]`, so it doesn't need an allocator and can be used from
+//! firmware builds that disable the `std` feature.
+use core::fmt::Display;
+use core::str::FromStr;
 
-use anyhow::bail;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "std")]
+use crate::codec::{eof_at, CountingReader, Decode, DecodeError, Encode};
 
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct ChunkType([u8; 4]);
 
 const FIFTH_BIT: u8 = 0b00100000;
 
+/// Chunk-type parsing errors. Carries no allocation, so it stays usable
+/// in `no_std` builds; under the `std` feature it also satisfies
+/// `std::error::Error`, which lets `?` convert it into `anyhow::Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkTypeError {
+    WrongLength,
+    NotAscii,
+    NotAlphabetic { offset: usize },
+}
+
+impl Display for ChunkTypeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongLength => write!(f, "string is not composed of 4 bytes"),
+            Self::NotAscii => write!(f, "string should be ascii"),
+            Self::NotAlphabetic { offset } => {
+                write!(f, "not an ascii alphabetic byte at index {}", offset)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChunkTypeError {}
+
 impl ChunkType {
     pub fn bytes(&self) -> [u8; 4] {
         self.0
@@ -34,18 +67,12 @@ impl ChunkType {
 }
 
 impl TryFrom<[u8; 4]> for ChunkType {
-    type Error = anyhow::Error;
+    type Error = ChunkTypeError;
 
     fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
         for (idx, val) in value.iter().enumerate() {
             if !val.is_ascii_alphabetic() {
-                bail!("not an ascii alphabetic byte")
-            }
-            // Third byte
-            if idx == 2 {
-                if (*val & FIFTH_BIT) == 1 {
-                    bail!("reserved bit not valid")
-                }
+                return Err(ChunkTypeError::NotAlphabetic { offset: idx });
             }
         }
         Ok(Self(value))
@@ -53,33 +80,60 @@ impl TryFrom<[u8; 4]> for ChunkType {
 }
 
 impl FromStr for ChunkType {
-    type Err = anyhow::Error;
+    type Err = ChunkTypeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.len() != 4 {
-            bail!("string is not composed of 4 bytes");
+            return Err(ChunkTypeError::WrongLength);
         }
         if !s.is_ascii() {
-            bail!("string should be ascii");
+            return Err(ChunkTypeError::NotAscii);
+        }
+
+        let mut value = [0u8; 4];
+        for (idx, c) in s.chars().enumerate() {
+            if !c.is_ascii_alphabetic() {
+                return Err(ChunkTypeError::NotAlphabetic { offset: idx });
+            }
+            value[idx] = c as u8;
         }
-        let value: [u8; 4] = s
-            .chars()
-            .map(|c| {
-                if !c.is_ascii_alphabetic() {
-                    bail!("not an ascii alphabetic byte");
-                }
-                Ok(c as u8)
-            })
-            .collect::<Result<Vec<_>, Self::Err>>()?
-            .try_into()
-            .unwrap();
 
         value.try_into()
     }
 }
 
+#[cfg(feature = "std")]
+impl Decode for ChunkType {
+    fn decode(reader: &mut CountingReader<impl Read>) -> anyhow::Result<Self> {
+        let offset = reader.offset();
+        let mut buf = [0u8; 4];
+        reader
+            .read_exact(&mut buf)
+            .map_err(|e| eof_at(e, offset, "chunk type"))?;
+
+        Self::try_from(buf).map_err(|e| match e {
+            ChunkTypeError::NotAlphabetic { offset: byte } => {
+                DecodeError::new(offset + byte as u64, "non-alphabetic chunk-type byte").into()
+            }
+            other => DecodeError::new(offset, other.to_string()).into(),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Encode for ChunkType {
+    fn encode(&self, writer: &mut impl Write) -> anyhow::Result<()> {
+        writer.write_all(&self.0)?;
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
+}
+
 impl Display for ChunkType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for c in self.0.iter() {
             write!(f, "{}", *c as char)?;
         }
@@ -87,7 +141,7 @@ impl Display for ChunkType {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use std::convert::TryFrom;
@@ -177,6 +231,34 @@ mod tests {
         assert_eq!(&chunk.to_string(), "RuSt");
     }
 
+    #[test]
+    pub fn test_chunk_type_try_from_reports_bad_byte_index() {
+        let err = ChunkType::try_from([82, 117, 49, 116]).unwrap_err();
+        assert_eq!(err, ChunkTypeError::NotAlphabetic { offset: 2 });
+    }
+
+    #[test]
+    pub fn test_chunk_type_decode_encode_round_trip() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let mut buf = Vec::new();
+        chunk_type.encode(&mut buf).unwrap();
+        assert_eq!(chunk_type.encoded_len(), buf.len());
+
+        let mut reader = CountingReader::new(buf.as_slice());
+        let decoded = ChunkType::decode(&mut reader).unwrap();
+        assert_eq!(chunk_type, decoded);
+    }
+
+    #[test]
+    pub fn test_chunk_type_decode_reports_offset_of_bad_byte() {
+        let buf = [82, 117, 49, 116]; // '1' at index 2 is not alphabetic
+        let mut reader = CountingReader::new(buf.as_slice());
+        let err = ChunkType::decode(&mut reader).unwrap_err();
+
+        let decode_err = err.downcast_ref::<DecodeError>().unwrap();
+        assert_eq!(decode_err.offset, 2);
+    }
+
     #[test]
     pub fn test_chunk_type_trait_impls() {
         let chunk_type_1: ChunkType = TryFrom::try_from([82, 117, 83, 116]).unwrap();
@@ -0,0 +1,78 @@
+use std::fmt::{self, Display};
+use std::io::{Read, Write};
+
+/// Wraps a reader and counts how many bytes have been pulled through it,
+/// so that `Decode` impls can report the byte offset a parse failure
+/// happened at instead of an offset-free message.
+pub(crate) struct CountingReader<R> {
+    inner: R,
+    offset: u64,
+}
+
+impl<R: Read> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0 }
+    }
+
+    /// Number of bytes read so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+}
+
+/// A decode failure tagged with the byte offset it happened at, so a
+/// corrupt file can be pinpointed instead of just described.
+#[derive(Debug)]
+pub(crate) struct DecodeError {
+    pub offset: u64,
+    pub message: String,
+}
+
+impl DecodeError {
+    pub fn new(offset: u64, message: impl Into<String>) -> Self {
+        Self {
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Maps an `UnexpectedEof` from `read_exact` into an offset-annotated
+/// "truncated" `DecodeError`; other I/O errors are passed through as-is.
+pub(crate) fn eof_at(err: std::io::Error, offset: u64, what: &str) -> anyhow::Error {
+    if err.kind() == std::io::ErrorKind::UnexpectedEof {
+        DecodeError::new(offset, format!("truncated {}", what)).into()
+    } else {
+        err.into()
+    }
+}
+
+/// Decodes a value from a byte stream, reporting the offset a failure
+/// happened at via [`DecodeError`] rather than an offset-free message.
+pub(crate) trait Decode: Sized {
+    fn decode(reader: &mut CountingReader<impl Read>) -> anyhow::Result<Self>;
+}
+
+/// Encodes a value to a byte stream.
+pub(crate) trait Encode {
+    fn encode(&self, writer: &mut impl Write) -> anyhow::Result<()>;
+
+    /// Number of bytes `encode` writes, without actually writing them.
+    fn encoded_len(&self) -> usize;
+}
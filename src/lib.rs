@@ -0,0 +1,6 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod chunk;
+mod chunk_type;
+#[cfg(feature = "std")]
+mod codec;